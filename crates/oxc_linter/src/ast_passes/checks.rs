@@ -0,0 +1,372 @@
+use oxc_ast::ast::*;
+use oxc_ast_visit::{Visit, walk};
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::{Atom, Span};
+
+fn return_outside_function(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("'return' statement is only valid inside a function").with_label(span)
+}
+
+fn await_outside_async(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("'await' is only valid inside an async function").with_label(span)
+}
+
+fn yield_outside_generator(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("'yield' is only valid inside a generator function").with_label(span)
+}
+
+fn super_outside_method(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("'super' is only valid inside a method, a class field initializer, or a static block")
+        .with_label(span)
+}
+
+fn continue_target_not_a_loop(label: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("label '{label}' is not the label of a loop"))
+        .with_help("a 'continue' label must refer to an enclosing loop, not just any labeled statement")
+        .with_label(span)
+}
+
+fn accessor_wrong_param_count(kind: &str, expected: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!("a '{kind}' must have {expected}")).with_label(span)
+}
+
+fn duplicate_proto(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("duplicate '__proto__' property in object literal is not allowed")
+        .with_label(span)
+}
+
+fn new_target_outside_function(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("'new.target' is only valid inside a function").with_label(span)
+}
+
+/// One entry per function currently being walked: only the bits needed to
+/// resolve `return`/`await`/`yield`/`new.target` validity for nodes inside
+/// it. Arrow functions are transparent to `yield` and `new.target` (they
+/// have no generator-ness or `[[NewTarget]]` slot of their own), so those
+/// two checks skip over arrow entries to find the nearest real function.
+struct FunctionScope {
+    is_async: bool,
+    is_generator: bool,
+    is_arrow: bool,
+}
+
+/// A single traversal that flags parseable-but-illegal constructs. See the
+/// [module docs](super) for why this is separate from the configurable
+/// rules in [`crate::rules`].
+///
+/// This walks the AST directly with [`Visit`] rather than going through
+/// `crate::context::LintContext`: the whole point of this pass is that it
+/// runs before semantic analysis, so it cannot depend on anything that
+/// `Semantic` provides (scopes, the `AstNodes` arena, symbol resolution).
+/// All the ancestor context each check needs -- "am I inside a function",
+/// "am I inside a method" -- is tracked on small stacks here instead.
+#[derive(Default)]
+pub(crate) struct AstValidator<'a> {
+    function_stack: Vec<FunctionScope>,
+    /// `true` while visiting a context where `super` may be referenced:
+    /// inside a method body, a class field initializer, or a static block.
+    /// Plain (non-arrow) functions reset this to `false` for their own
+    /// body, since they bind their own `this`/`super` unrelated to any
+    /// enclosing method.
+    super_allowed_stack: Vec<bool>,
+    /// Set just before descending into a method-like function body (method,
+    /// getter, setter, or object-literal shorthand method), consumed by the
+    /// next `visit_function` call to seed `super_allowed_stack`.
+    pending_method_super_context: bool,
+    /// `(label name, is the label's target a loop)`, pushed on entering a
+    /// `LabeledStatement` and popped on leaving it.
+    label_stack: Vec<(Atom<'a>, bool)>,
+    diagnostics: Vec<OxcDiagnostic>,
+}
+
+impl<'a> AstValidator<'a> {
+    /// Runs the validator over `program` and returns every diagnostic it
+    /// found. It needs only the parsed `program`, not `Semantic`; nothing
+    /// outside this module's tests calls it.
+    pub(crate) fn check_program(program: &Program<'a>) -> Vec<OxcDiagnostic> {
+        let mut validator = Self { super_allowed_stack: vec![false], ..Self::default() };
+        validator.visit_program(program);
+        validator.diagnostics
+    }
+
+    fn nearest_non_arrow(&self) -> Option<&FunctionScope> {
+        self.function_stack.iter().rev().find(|scope| !scope.is_arrow)
+    }
+
+    fn check_return(&mut self, stmt: &ReturnStatement) {
+        if self.function_stack.is_empty() {
+            self.diagnostics.push(return_outside_function(stmt.span));
+        }
+    }
+
+    fn check_await(&mut self, expr: &AwaitExpression) {
+        match self.function_stack.last() {
+            Some(scope) if scope.is_async => {}
+            Some(_) => self.diagnostics.push(await_outside_async(expr.span)),
+            // Top-level await is legal in modules; leave that determination
+            // to the module-record-aware rules and only flag the
+            // unambiguous case of `await` inside a non-async function.
+            None => {}
+        }
+    }
+
+    fn check_yield(&mut self, expr: &YieldExpression) {
+        match self.nearest_non_arrow() {
+            Some(scope) if scope.is_generator => {}
+            _ => self.diagnostics.push(yield_outside_generator(expr.span)),
+        }
+    }
+
+    fn check_super(&mut self, sup: &Super) {
+        if !self.super_allowed_stack.last().copied().unwrap_or(false) {
+            self.diagnostics.push(super_outside_method(sup.span));
+        }
+    }
+
+    fn check_continue_target(&mut self, stmt: &ContinueStatement) {
+        let Some(label) = &stmt.label else { return };
+        if let Some((_, is_loop)) = self.label_stack.iter().rev().find(|(name, _)| *name == label.name) {
+            if !is_loop {
+                self.diagnostics.push(continue_target_not_a_loop(&label.name, stmt.span));
+            }
+        }
+    }
+
+    fn check_params_for_accessor(&mut self, kind: PropertyKind, params: &FormalParameters, span: Span) {
+        match kind {
+            PropertyKind::Get if !params.items.is_empty() || params.rest.is_some() => {
+                self.diagnostics.push(accessor_wrong_param_count("getter", "no parameters", span));
+            }
+            PropertyKind::Set if params.items.len() != 1 || params.rest.is_some() => {
+                self.diagnostics
+                    .push(accessor_wrong_param_count("setter", "exactly one parameter", span));
+            }
+            _ => {}
+        }
+    }
+
+    fn check_duplicate_proto(&mut self, obj: &ObjectExpression) {
+        let mut seen = false;
+        for prop in &obj.properties {
+            let ObjectPropertyKind::ObjectProperty(prop) = prop else { continue };
+            if prop.kind != PropertyKind::Init || prop.computed || prop.shorthand || prop.method {
+                continue;
+            }
+            let is_proto = match &prop.key {
+                PropertyKey::StaticIdentifier(ident) => ident.name == "__proto__",
+                PropertyKey::StringLiteral(lit) => lit.value == "__proto__",
+                _ => false,
+            };
+            if !is_proto {
+                continue;
+            }
+            if seen {
+                self.diagnostics.push(duplicate_proto(prop.span));
+            }
+            seen = true;
+        }
+    }
+
+    fn check_new_target(&mut self, meta: &MetaProperty) {
+        if meta.meta.name != "new" || meta.property.name != "target" {
+            return;
+        }
+        if self.nearest_non_arrow().is_none() {
+            self.diagnostics.push(new_target_outside_function(meta.span));
+        }
+    }
+
+    /// A labeled statement's body is a loop if it directly is one, or if
+    /// it's itself a (possibly multiply-)labeled loop, e.g. `outer: inner:
+    /// for (;;) {}`.
+    fn body_is_loop(body: &Statement) -> bool {
+        match body {
+            Statement::ForStatement(_)
+            | Statement::WhileStatement(_)
+            | Statement::DoWhileStatement(_)
+            | Statement::ForInStatement(_)
+            | Statement::ForOfStatement(_) => true,
+            Statement::LabeledStatement(labeled) => Self::body_is_loop(&labeled.body),
+            _ => false,
+        }
+    }
+}
+
+impl<'a> Visit<'a> for AstValidator<'a> {
+    fn visit_function(&mut self, func: &Function<'a>, flags: oxc_semantic::ScopeFlags) {
+        let is_method_body = std::mem::take(&mut self.pending_method_super_context);
+        self.super_allowed_stack.push(is_method_body);
+        self.function_stack.push(FunctionScope {
+            is_async: func.r#async,
+            is_generator: func.generator,
+            is_arrow: false,
+        });
+        walk::walk_function(self, func, flags);
+        self.function_stack.pop();
+        self.super_allowed_stack.pop();
+    }
+
+    fn visit_arrow_function_expression(&mut self, func: &ArrowFunctionExpression<'a>) {
+        // Arrows don't bind their own `super`/`this`, so `super_allowed_stack`
+        // is left untouched -- they're transparent to the enclosing context.
+        self.function_stack.push(FunctionScope {
+            is_async: func.r#async,
+            is_generator: false,
+            is_arrow: true,
+        });
+        walk::walk_arrow_function_expression(self, func);
+        self.function_stack.pop();
+    }
+
+    fn visit_method_definition(&mut self, method: &MethodDefinition<'a>) {
+        self.pending_method_super_context = true;
+        let kind = match method.kind {
+            MethodDefinitionKind::Get => Some(PropertyKind::Get),
+            MethodDefinitionKind::Set => Some(PropertyKind::Set),
+            MethodDefinitionKind::Method | MethodDefinitionKind::Constructor => None,
+        };
+        if let Some(kind) = kind {
+            self.check_params_for_accessor(kind, &method.value.params, method.span);
+        }
+        walk::walk_method_definition(self, method);
+    }
+
+    fn visit_property_definition(&mut self, prop: &PropertyDefinition<'a>) {
+        self.super_allowed_stack.push(true);
+        walk::walk_property_definition(self, prop);
+        self.super_allowed_stack.pop();
+    }
+
+    fn visit_static_block(&mut self, block: &StaticBlock<'a>) {
+        self.super_allowed_stack.push(true);
+        walk::walk_static_block(self, block);
+        self.super_allowed_stack.pop();
+    }
+
+    fn visit_object_property(&mut self, prop: &ObjectProperty<'a>) {
+        if prop.method || matches!(prop.kind, PropertyKind::Get | PropertyKind::Set) {
+            self.pending_method_super_context = true;
+            if matches!(prop.kind, PropertyKind::Get | PropertyKind::Set) {
+                if let Expression::FunctionExpression(func) = &prop.value {
+                    self.check_params_for_accessor(prop.kind, &func.params, prop.span);
+                }
+            }
+        }
+        walk::walk_object_property(self, prop);
+    }
+
+    fn visit_labeled_statement(&mut self, stmt: &LabeledStatement<'a>) {
+        self.label_stack.push((stmt.label.name.clone(), Self::body_is_loop(&stmt.body)));
+        walk::walk_labeled_statement(self, stmt);
+        self.label_stack.pop();
+    }
+
+    fn visit_return_statement(&mut self, stmt: &ReturnStatement<'a>) {
+        self.check_return(stmt);
+        walk::walk_return_statement(self, stmt);
+    }
+
+    fn visit_await_expression(&mut self, expr: &AwaitExpression<'a>) {
+        self.check_await(expr);
+        walk::walk_await_expression(self, expr);
+    }
+
+    fn visit_yield_expression(&mut self, expr: &YieldExpression<'a>) {
+        self.check_yield(expr);
+        walk::walk_yield_expression(self, expr);
+    }
+
+    fn visit_super(&mut self, sup: &Super) {
+        self.check_super(sup);
+    }
+
+    fn visit_continue_statement(&mut self, stmt: &ContinueStatement<'a>) {
+        self.check_continue_target(stmt);
+    }
+
+    fn visit_object_expression(&mut self, obj: &ObjectExpression<'a>) {
+        self.check_duplicate_proto(obj);
+        walk::walk_object_expression(self, obj);
+    }
+
+    fn visit_meta_property(&mut self, meta: &MetaProperty<'a>) {
+        self.check_new_target(meta);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    use super::AstValidator;
+
+    fn check(source: &str) -> Vec<String> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::mjs()).parse();
+        AstValidator::check_program(&ret.program).into_iter().map(|d| d.message.to_string()).collect()
+    }
+
+    #[test]
+    fn return_outside_function_is_reported() {
+        assert_eq!(check("return 1;").len(), 1);
+        assert!(check("function f() { return 1; }").is_empty());
+        assert!(check("const f = () => { return 1; };").is_empty());
+    }
+
+    #[test]
+    fn yield_requires_a_generator_not_just_any_enclosing_function() {
+        assert_eq!(check("function f() { yield 1; }").len(), 1);
+        assert!(check("function* f() { yield 1; }").is_empty());
+        // Arrows have no generator-ness of their own, so `check_yield`
+        // resolves a `yield` textually inside one against the nearest
+        // non-arrow function -- here the generator, so nothing is reported.
+        // The parser itself rejects `yield` inside an arrow body, so this
+        // source never yields a `YieldExpression` there in the first place.
+        assert!(check("function* f() { const g = () => { yield 1; }; }").is_empty());
+    }
+
+    #[test]
+    fn await_requires_the_immediately_enclosing_function_to_be_async() {
+        assert_eq!(check("function f() { await g(); }").len(), 1);
+        assert!(check("async function f() { await g(); }").is_empty());
+    }
+
+    #[test]
+    fn super_is_allowed_in_field_initializers_and_static_blocks_not_just_methods() {
+        assert!(check("class C extends D { x = super.y; }").is_empty());
+        assert!(check("class C extends D { static { super.y; } }").is_empty());
+        assert!(check("class C extends D { m() { super.y(); } }").is_empty());
+        assert_eq!(check("super.y();").len(), 1);
+        // a plain function nested in a method does not inherit `super`.
+        assert_eq!(check("class C extends D { m() { (function () { super.y(); })(); } }").len(), 1);
+    }
+
+    #[test]
+    fn continue_must_target_a_loop_label() {
+        assert_eq!(check("outer: { continue outer; }").len(), 1);
+        assert!(check("outer: for (;;) { continue outer; }").is_empty());
+        assert!(check("outer: inner: for (;;) { continue outer; }").is_empty());
+    }
+
+    #[test]
+    fn accessor_arity_is_checked() {
+        assert_eq!(check("const o = { get x(a) { return a; } };").len(), 1);
+        assert_eq!(check("const o = { set x() {} };").len(), 1);
+        assert!(check("const o = { get x() { return 1; }, set x(v) {} };").is_empty());
+    }
+
+    #[test]
+    fn duplicate_proto_is_reported_once_per_extra_occurrence() {
+        assert_eq!(check("const o = { __proto__: a, __proto__: b };").len(), 1);
+        assert!(check("const o = { __proto__: a, [\"__proto__\"]: b };").is_empty());
+    }
+
+    #[test]
+    fn new_target_requires_an_enclosing_function() {
+        assert_eq!(check("new.target;").len(), 1);
+        assert!(check("function f() { new.target; }").is_empty());
+        assert!(check("function f() { const g = () => new.target; }").is_empty());
+    }
+}
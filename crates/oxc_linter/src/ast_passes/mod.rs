@@ -0,0 +1,22 @@
+//! Post-parse AST validation.
+//!
+//! This is a single traversal over the frozen AST, run immediately after
+//! parsing and before scope/type resolution, that flags constructs the oxc
+//! AST is *structurally* able to represent but that the language forbids --
+//! for example `return` outside of a function. These are shape checks, not
+//! semantic ones: each one only needs the node and its ancestors, never
+//! bindings, scopes, or types.
+//!
+//! Because these are static correctness checks rather than configurable
+//! style rules, they do not live in [`crate::rules`] and cannot be disabled.
+//! They share a single traversal so the many small checks here don't each
+//! pay for their own walk of the tree.
+//!
+//! [`AstValidator::check_program`] needs only the parsed program, so it can
+//! run before `Semantic` (and therefore every configurable rule). `Linter`
+//! does not call it, so its diagnostics are not part of a file's lint
+//! results; this pass is exercised directly by the tests in `checks`.
+
+mod checks;
+
+pub(crate) use checks::AstValidator;
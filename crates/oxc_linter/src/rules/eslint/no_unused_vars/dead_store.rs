@@ -0,0 +1,326 @@
+//! Dead-store detection for [`NoUnusedVars`], via a backward liveness
+//! dataflow over the control-flow graph built by `oxc_semantic`.
+//!
+//! A *dead store* is different from an unused *binding*: the variable is
+//! read somewhere, but a particular assignment to it is never observed
+//! before the variable is either reassigned or goes out of scope.
+//!
+//! ```js
+//! let x = 1; // not reported: a declarator's initializer is not analyzed
+//! x = compute(); // dead: this value is never read
+//! return; // had this been `return x + 1;`, the store above would be live
+//! ```
+//!
+//! The analysis is the standard backward dataflow over basic blocks:
+//!
+//! ```text
+//! live_out(b) = ⋃ live_in(s), for each successor s of b
+//! live_in(b)  = use(b) ∪ (live_out(b) − def(b))
+//! ```
+//!
+//! iterated to a fixpoint, since loops introduce back-edges that a single
+//! backward pass cannot resolve in one go.
+
+use std::collections::HashMap;
+
+use oxc_cfg::{
+    BasicBlockId, EdgeType,
+    petgraph::{Direction, visit::EdgeRef},
+};
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_semantic::{NodeId, Semantic, SymbolFlags};
+use oxc_span::Span;
+
+use crate::context::LintContext;
+
+use super::Symbol;
+
+fn dead_store_diagnostic(name: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!("value assigned to '{name}' is never read"))
+        .with_help(
+            "either remove this assignment or use the value before it is reassigned or the variable goes out of scope",
+        )
+        .with_label(span)
+}
+
+/// Runs the liveness analysis for `symbol` and reports each dead store it
+/// finds. `NoUnusedVars` does not call this; the tests below exercise
+/// [`LivenessAnalysis`] directly.
+pub(crate) fn report_dead_stores<'a>(
+    ctx: &LintContext<'a>,
+    semantic: &Semantic<'a>,
+    symbol: &Symbol<'_, 'a>,
+) {
+    let name = symbol.name();
+    for (_node_id, span) in LivenessAnalysis::new(semantic).dead_stores(symbol) {
+        ctx.diagnostic(dead_store_diagnostic(&name, span));
+    }
+}
+
+/// Per-block liveness facts for a single variable under analysis.
+#[derive(Debug, Default, Clone, Copy)]
+struct BlockFacts {
+    /// `true` if the block reads the variable anywhere, before or after a
+    /// write to it. This over-approximates `use(b)`, which can only make
+    /// the variable look live, never dead.
+    used_before_def: bool,
+    /// `true` if the block writes to the variable at all (this is `def(b)`).
+    def: bool,
+    /// Result of the fixpoint iteration: is the variable live when control
+    /// leaves this block?
+    live_out: bool,
+}
+
+/// Runs a backward liveness dataflow for a single [`Symbol`] to find the
+/// writes to it that are dead stores.
+pub(crate) struct LivenessAnalysis<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+}
+
+impl<'s, 'a> LivenessAnalysis<'s, 'a> {
+    pub(crate) fn new(semantic: &'s Semantic<'a>) -> Self {
+        Self { semantic }
+    }
+
+    /// Returns the [`NodeId`] and [`Span`] of every write to `symbol` that is
+    /// a dead store.
+    ///
+    /// Two cases are conservatively never reported:
+    /// - the symbol is exported (checked directly via its `SymbolFlags`) or
+    ///   lies inside an ambient `declare` namespace or module (checked via
+    ///   [`Symbol::is_in_declared_module`]), since it is observable outside
+    ///   the analyzed region and so is always live at scope exit. Other
+    ///   `declare`d bindings are not exempted.
+    /// - the symbol is captured by a closure, since the closure may run at
+    ///   any point after it is created; a closure expression is therefore
+    ///   treated as a *use* of every free variable it captures, keeping
+    ///   those variables live across its entire lifetime.
+    pub(crate) fn dead_stores(&self, symbol: &Symbol<'_, 'a>) -> Vec<(NodeId, Span)> {
+        if symbol.is_in_declared_module() || self.is_exported(symbol) {
+            return vec![];
+        }
+
+        let Some(cfg) = self.semantic.cfg() else {
+            // No CFG was built for this file (`SemanticBuilder` ran without
+            // `with_cfg(true)`). Be conservative and report nothing.
+            return vec![];
+        };
+
+        let writes = self.writes(symbol);
+        if writes.is_empty() {
+            return vec![];
+        }
+
+        if self.is_captured_by_closure(symbol) {
+            return vec![];
+        }
+
+        let reads = self.reads(symbol);
+
+        let blocks: Vec<BasicBlockId> = cfg.graph.node_indices().collect();
+        let mut facts: HashMap<BasicBlockId, BlockFacts> = blocks
+            .iter()
+            .map(|&block| (block, self.block_facts(block, &writes, &reads)))
+            .collect();
+
+        // live_out(b) = ⋃ live_in(s) for each successor s of b. Iterate
+        // until nothing changes -- necessary because loops create
+        // back-edges that one backward pass over the blocks cannot settle.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &block in &blocks {
+                let live_out = cfg
+                    .graph
+                    .edges_directed(block, Direction::Outgoing)
+                    .filter(|edge| !matches!(edge.weight(), EdgeType::Unreachable))
+                    .any(|edge| {
+                        let successor = facts[&edge.target()];
+                        successor.used_before_def || (successor.live_out && !successor.def)
+                    });
+
+                let entry = facts.get_mut(&block).expect("block facts computed for every block");
+                if entry.live_out != live_out {
+                    entry.live_out = live_out;
+                    changed = true;
+                }
+            }
+        }
+
+        // A write is a dead store when nothing after it, in program order
+        // within its own block, reads the variable, and the block's
+        // `live_out` is false (i.e. no successor may read it either).
+        writes
+            .into_iter()
+            .filter(|(_, block, node_id, _)| {
+                let live_out = facts.get(block).is_some_and(|f| f.live_out);
+                !live_out && !Self::read_follows_in_block(node_id, block, &reads)
+            })
+            .map(|(span, _, node_id, _)| (node_id, span))
+            .collect()
+    }
+
+    /// Returns `true` if `symbol` is exported, and therefore observable (and
+    /// so implicitly "read") from outside the module regardless of whether
+    /// anything in this file reads it.
+    fn is_exported(&self, symbol: &Symbol<'_, 'a>) -> bool {
+        symbol.scoping().symbol_flags(symbol.symbol_id()).contains(SymbolFlags::Export)
+    }
+
+    /// Returns `true` if `symbol` is referenced from inside any closure
+    /// (function or arrow function, immediately invoked or not) -- in which
+    /// case we cannot know when (or whether) it runs relative to a write,
+    /// so the variable must be treated as live for the writes' entire
+    /// enclosing scope.
+    fn is_captured_by_closure(&self, symbol: &Symbol<'_, 'a>) -> bool {
+        let scoping = symbol.scoping();
+        scoping.get_resolved_references(symbol.symbol_id()).any(|reference| {
+            for node in symbol.nodes().ancestors(reference.node_id()) {
+                if matches!(
+                    node.kind(),
+                    oxc_ast::AstKind::Function(_) | oxc_ast::AstKind::ArrowFunctionExpression(_)
+                ) {
+                    return true;
+                }
+                // A `Function`/`ArrowFunctionExpression` node itself
+                // reports the scope *enclosing* it, not the one it creates,
+                // so a closure declared in the same scope as `symbol`
+                // reaches this node -- and must be checked against it --
+                // before this condition ever becomes true. Stop afterwards:
+                // going further up would walk past the symbol's own scope.
+                if node.scope_id() == symbol.scope_id() {
+                    return false;
+                }
+            }
+            false
+        })
+    }
+
+    /// Collects every write (assignment or update expression) to `symbol`
+    /// that shows up as a resolved reference, tagged with the basic block
+    /// that contains it. A declarator's own initializer is not a reference
+    /// and so is never included here: an unread initializer is never
+    /// reported as a dead store (see the module docs above).
+    fn writes(&self, symbol: &Symbol<'_, 'a>) -> Vec<(Span, BasicBlockId, NodeId, ())> {
+        let scoping = symbol.scoping();
+        let cfg = self.semantic.cfg().expect("checked by caller");
+        scoping
+            .get_resolved_references(symbol.symbol_id())
+            .filter(|reference| reference.is_write())
+            .filter_map(|reference| {
+                let node_id = reference.node_id();
+                let node = symbol.nodes().get_node(node_id);
+                let block = cfg.basic_block_for_node(node_id)?;
+                Some((node.kind().span(), block, node_id, ()))
+            })
+            .collect()
+    }
+
+    /// Collects the [`NodeId`]s of every read of `symbol`, tagged with the
+    /// basic block that contains it.
+    fn reads(&self, symbol: &Symbol<'_, 'a>) -> Vec<(NodeId, BasicBlockId)> {
+        let scoping = symbol.scoping();
+        let cfg = self.semantic.cfg().expect("checked by caller");
+        scoping
+            .get_resolved_references(symbol.symbol_id())
+            .filter(|reference| reference.is_read())
+            .filter_map(|reference| {
+                let node_id = reference.node_id();
+                let block = cfg.basic_block_for_node(node_id)?;
+                Some((node_id, block))
+            })
+            .collect()
+    }
+
+    fn block_facts(
+        &self,
+        block: BasicBlockId,
+        writes: &[(Span, BasicBlockId, NodeId, ())],
+        reads: &[(NodeId, BasicBlockId)],
+    ) -> BlockFacts {
+        // `use(b)`: does a read occur anywhere in this block? Read/write
+        // order within the block is not checked, so a read that follows a
+        // write still counts; that can only make the variable look live at
+        // block entry. Order within a block is applied separately in
+        // `read_follows_in_block` when deciding if a specific write is dead.
+        let used_before_def = reads.iter().any(|(_, b)| *b == block);
+        let def = writes.iter().any(|(_, b, ..)| *b == block);
+        BlockFacts { used_before_def, def, live_out: false }
+    }
+
+    /// Returns `true` if a read of the variable occurs after `node_id`
+    /// within `block`, in instruction order.
+    fn read_follows_in_block(
+        node_id: &NodeId,
+        block: &BasicBlockId,
+        reads: &[(NodeId, BasicBlockId)],
+    ) -> bool {
+        reads.iter().any(|(read_id, read_block)| read_block == block && read_id.index() > node_id.index())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_semantic::SemanticBuilder;
+    use oxc_span::SourceType;
+
+    use super::{LivenessAnalysis, Symbol};
+
+    /// Parses `source`, builds its `Semantic`, and returns the spans of
+    /// every dead store found across every symbol in the file.
+    fn dead_store_spans(source: &str) -> Vec<String> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::mjs()).parse();
+        let semantic = SemanticBuilder::new().with_cfg(true).build(&ret.program).semantic;
+        let analysis = LivenessAnalysis::new(&semantic);
+
+        semantic
+            .scoping()
+            .symbol_ids()
+            .flat_map(|symbol_id| {
+                let symbol = Symbol::new(&semantic, symbol_id);
+                analysis
+                    .dead_stores(&symbol)
+                    .into_iter()
+                    .map(|(_, span)| span.source_text(source).to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports_a_simple_dead_store() {
+        let spans = dead_store_spans("function f() { let x = 1; x = g(); return; }");
+        assert_eq!(spans, vec!["x"]);
+    }
+
+    #[test]
+    fn does_not_report_a_store_that_is_later_read() {
+        let spans = dead_store_spans("function f() { let x = 1; x = g(); return x; }");
+        assert!(spans.is_empty(), "expected no dead stores, got {spans:?}");
+    }
+
+    #[test]
+    fn does_not_report_a_store_live_through_a_loop_back_edge() {
+        let spans = dead_store_spans(
+            "function f() { let x = 0; while (cond()) { x = x + 1; } return x; }",
+        );
+        assert!(spans.is_empty(), "expected no dead stores, got {spans:?}");
+    }
+
+    #[test]
+    fn does_not_report_exported_bindings() {
+        let spans = dead_store_spans("export let x = 1; x = g();");
+        assert!(spans.is_empty(), "expected no dead stores, got {spans:?}");
+    }
+
+    #[test]
+    fn does_not_report_writes_captured_by_a_closure() {
+        let spans = dead_store_spans(
+            "function f() { let x = 1; const read = () => x; x = g(); return read; }",
+        );
+        assert!(spans.is_empty(), "expected no dead stores, got {spans:?}");
+    }
+}
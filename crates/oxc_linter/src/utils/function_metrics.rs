@@ -0,0 +1,432 @@
+//! A shared metrics pass backing the family of complexity-style rules
+//! (`complexity`, `max-statements`, `max-depth`, `max-nested-callbacks`).
+//!
+//! Each of those rules wants a different count derived from the same walk
+//! of a function's body, so instead of every rule re-traversing the tree,
+//! this module walks each function once and records the counts that any of
+//! them might need. Rules then read their own threshold out of
+//! [`FunctionMetrics`] instead of visiting anything themselves, mirroring
+//! the dedicated AST-node-counting pass oxc already uses elsewhere for
+//! rules that need a single shared count.
+//!
+//! Each of `complexity`, `max-statements`, `max-depth`, and
+//! `max-nested-callbacks` is meant to call [`compute_function_metrics`]
+//! once per file (typically caching the result on first use, the same way
+//! other shared per-file computations are) and then, for each
+//! `AstKind::Function`/`AstKind::ArrowFunctionExpression`/`AstKind::Program`
+//! node it visits, look up `FunctionMetricsMap::get(scope_id)` and compare
+//! the relevant field against its own configured threshold. None of those
+//! four rules exist in this tree yet, so for now this module is exercised
+//! directly by its own tests below.
+
+use oxc_ast::ast::*;
+use oxc_ast_visit::{Visit, walk};
+use oxc_semantic::ScopeId;
+use rustc_hash::FxHashMap;
+
+/// Counts accumulated for a single function body.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FunctionMetrics {
+    /// Total number of statements directly executed in this function,
+    /// not counting statements that belong to a nested function.
+    pub statement_count: usize,
+    /// Cyclomatic complexity: `1 + ` the number of branching points
+    /// (`if`, `for`, `while`, `do-while`, `case`, `catch`, `&&`, `||`,
+    /// `??`, and the conditional `?:` operator).
+    pub cyclomatic_complexity: usize,
+    /// Maximum block-nesting depth reached inside this function. Nested
+    /// functions start their own count and do not contribute to this one.
+    pub max_depth: usize,
+    /// Maximum depth of function expressions passed as callback arguments,
+    /// nested within this function (e.g. `a(() => b(() => c()))` has a
+    /// nested-callback depth of 2).
+    pub max_nested_callbacks: usize,
+}
+
+/// The metrics for every function in a file, keyed by the [`ScopeId`] of its
+/// body -- the same id a rule already has on hand via
+/// `AstKind::Function::scope_id` or `AstKind::ArrowFunctionExpression::scope_id`.
+#[derive(Debug, Default)]
+pub struct FunctionMetricsMap(FxHashMap<ScopeId, FunctionMetrics>);
+
+impl FunctionMetricsMap {
+    pub fn get(&self, scope_id: ScopeId) -> Option<&FunctionMetrics> {
+        self.0.get(&scope_id)
+    }
+}
+
+/// Walks `program` once, computing [`FunctionMetrics`] for every function
+/// plus the top-level script/module body itself, keyed by
+/// `program.scope_id` -- ESLint's `max-nested-callbacks` (among others)
+/// fires just as readily on callbacks nested directly in top-level code as
+/// on ones nested inside a named function.
+pub fn compute_function_metrics(program: &Program) -> FunctionMetricsMap {
+    let mut collector = MetricsCollector {
+        stack: vec![],
+        callback_depth: 0,
+        pending_callback: false,
+        metrics: FxHashMap::default(),
+    };
+    let program_scope_id =
+        program.scope_id.get().expect("scope_id is populated after semantic analysis");
+    collector.enter_function(program_scope_id, false);
+    collector.visit_program(program);
+    collector.exit_function(false);
+    FunctionMetricsMap(collector.metrics)
+}
+
+/// One in-progress [`FunctionMetrics`] per function currently being walked.
+/// Pushed on entering a function and popped (into `metrics`) on leaving it,
+/// so that a nested function's counts never leak into its enclosing
+/// function's.
+struct FunctionFrame {
+    scope_id: ScopeId,
+    metrics: FunctionMetrics,
+    current_depth: usize,
+    /// The collector's `callback_depth` at the moment this frame was
+    /// pushed. Every later callback entry updates this frame's
+    /// `max_nested_callbacks` by the difference between the collector's
+    /// (shared, ever-growing-and-shrinking) depth and this base, which is
+    /// what makes the count relative to *this* function rather than to
+    /// whatever unrelated callback nesting happens to be live elsewhere.
+    base_callback_depth: usize,
+}
+
+struct MetricsCollector {
+    stack: Vec<FunctionFrame>,
+    /// How many callback functions (function expressions/arrows passed
+    /// directly as call arguments) are currently being walked, across the
+    /// *whole* traversal -- not per function frame. This must live outside
+    /// any single frame: a callback's own function frame is pushed only
+    /// *after* it is counted as a callback, so incrementing a per-frame
+    /// counter would always increment some other, now-stale frame instead
+    /// of surviving into the callback's own nested callbacks.
+    callback_depth: usize,
+    /// Set just before visiting a call argument that is itself a function
+    /// expression/arrow, consumed by the next `visit_function`/
+    /// `visit_arrow_function_expression` call.
+    pending_callback: bool,
+    metrics: FxHashMap<ScopeId, FunctionMetrics>,
+}
+
+impl MetricsCollector {
+    fn current(&mut self) -> Option<&mut FunctionFrame> {
+        self.stack.last_mut()
+    }
+
+    fn enter_function(&mut self, scope_id: ScopeId, is_callback_argument: bool) {
+        if is_callback_argument {
+            self.callback_depth += 1;
+            self.record_callback_depth();
+        }
+        self.stack.push(FunctionFrame {
+            scope_id,
+            metrics: FunctionMetrics { cyclomatic_complexity: 1, ..FunctionMetrics::default() },
+            current_depth: 0,
+            base_callback_depth: self.callback_depth,
+        });
+    }
+
+    fn exit_function(&mut self, is_callback_argument: bool) {
+        let frame = self.stack.pop().expect("exit_function without a matching enter_function");
+        self.metrics.insert(frame.scope_id, frame.metrics);
+        if is_callback_argument {
+            self.callback_depth -= 1;
+        }
+    }
+
+    /// Every function still on the stack gets a chance to record a deeper
+    /// `max_nested_callbacks`, not just the innermost one: `a(() => b(() =>
+    /// c()))` must report a depth of 2 on the function containing `a(...)`,
+    /// even though that function itself was not entered as a callback.
+    fn record_callback_depth(&mut self) {
+        let depth = self.callback_depth;
+        for frame in &mut self.stack {
+            let relative = depth - frame.base_callback_depth;
+            frame.metrics.max_nested_callbacks = frame.metrics.max_nested_callbacks.max(relative);
+        }
+    }
+
+    /// Depth is based on the control statements that ESLint's `max-depth`
+    /// treats as nestable (`if`/`for`/`while`/`do-while`/`switch`/`try`),
+    /// not on `BlockStatement` nodes: that would both miss brace-less
+    /// bodies like `if (a) b();` and over-count by one, since it would
+    /// also count the function's own top-level body block.
+    fn enter_nesting(&mut self) {
+        if let Some(frame) = self.current() {
+            frame.current_depth += 1;
+            frame.metrics.max_depth = frame.metrics.max_depth.max(frame.current_depth);
+        }
+    }
+
+    fn exit_nesting(&mut self) {
+        if let Some(frame) = self.current() {
+            frame.current_depth -= 1;
+        }
+    }
+
+    fn add_branch(&mut self) {
+        if let Some(frame) = self.current() {
+            frame.metrics.cyclomatic_complexity += 1;
+        }
+    }
+
+    fn add_statement(&mut self) {
+        if let Some(frame) = self.current() {
+            frame.metrics.statement_count += 1;
+        }
+    }
+}
+
+/// Is `argument` a function expression or arrow function passed *directly*
+/// as a call argument -- the definition of "callback" this metric uses, as
+/// opposed to one buried in some other expression (`a(x ? fn1 : fn2)` does
+/// not count).
+fn is_callback_argument(argument: &Argument) -> bool {
+    matches!(argument, Argument::FunctionExpression(_) | Argument::ArrowFunctionExpression(_))
+}
+
+impl<'a> Visit<'a> for MetricsCollector {
+    fn visit_function(&mut self, func: &Function<'a>, flags: oxc_semantic::ScopeFlags) {
+        let scope_id = func.scope_id.get().expect("scope_id is populated after semantic analysis");
+        let is_callback = std::mem::take(&mut self.pending_callback);
+        self.enter_function(scope_id, is_callback);
+        walk::walk_function(self, func, flags);
+        self.exit_function(is_callback);
+    }
+
+    fn visit_arrow_function_expression(&mut self, func: &ArrowFunctionExpression<'a>) {
+        let scope_id = func.scope_id.get().expect("scope_id is populated after semantic analysis");
+        let is_callback = std::mem::take(&mut self.pending_callback);
+        self.enter_function(scope_id, is_callback);
+        walk::walk_arrow_function_expression(self, func);
+        self.exit_function(is_callback);
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        self.visit_expression(&call.callee);
+        for argument in &call.arguments {
+            self.pending_callback = is_callback_argument(argument);
+            self.visit_argument(argument);
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement<'a>) {
+        // A `BlockStatement` used as a statement (`{ ... }`) is a container,
+        // not itself a countable statement -- each statement inside it is
+        // already counted individually when we recurse into it.
+        if !matches!(stmt, Statement::BlockStatement(_)) {
+            self.add_statement();
+        }
+        walk::walk_statement(self, stmt);
+    }
+
+    fn visit_if_statement(&mut self, stmt: &IfStatement<'a>) {
+        self.add_branch();
+        self.enter_nesting();
+        walk::walk_if_statement(self, stmt);
+        self.exit_nesting();
+    }
+
+    fn visit_for_statement(&mut self, stmt: &ForStatement<'a>) {
+        self.add_branch();
+        self.enter_nesting();
+        walk::walk_for_statement(self, stmt);
+        self.exit_nesting();
+    }
+
+    fn visit_for_in_statement(&mut self, stmt: &ForInStatement<'a>) {
+        self.add_branch();
+        self.enter_nesting();
+        walk::walk_for_in_statement(self, stmt);
+        self.exit_nesting();
+    }
+
+    fn visit_for_of_statement(&mut self, stmt: &ForOfStatement<'a>) {
+        self.add_branch();
+        self.enter_nesting();
+        walk::walk_for_of_statement(self, stmt);
+        self.exit_nesting();
+    }
+
+    fn visit_while_statement(&mut self, stmt: &WhileStatement<'a>) {
+        self.add_branch();
+        self.enter_nesting();
+        walk::walk_while_statement(self, stmt);
+        self.exit_nesting();
+    }
+
+    fn visit_do_while_statement(&mut self, stmt: &DoWhileStatement<'a>) {
+        self.add_branch();
+        self.enter_nesting();
+        walk::walk_do_while_statement(self, stmt);
+        self.exit_nesting();
+    }
+
+    fn visit_switch_statement(&mut self, stmt: &SwitchStatement<'a>) {
+        self.enter_nesting();
+        walk::walk_switch_statement(self, stmt);
+        self.exit_nesting();
+    }
+
+    fn visit_switch_case(&mut self, case: &SwitchCase<'a>) {
+        // ESLint's `complexity` rule counts every `case`, but explicitly
+        // excludes the `default` clause (its `test` is `None`).
+        if case.test.is_some() {
+            self.add_branch();
+        }
+        walk::walk_switch_case(self, case);
+    }
+
+    fn visit_try_statement(&mut self, stmt: &TryStatement<'a>) {
+        self.enter_nesting();
+        walk::walk_try_statement(self, stmt);
+        self.exit_nesting();
+    }
+
+    fn visit_catch_clause(&mut self, clause: &CatchClause<'a>) {
+        self.add_branch();
+        walk::walk_catch_clause(self, clause);
+    }
+
+    fn visit_conditional_expression(&mut self, expr: &ConditionalExpression<'a>) {
+        self.add_branch();
+        walk::walk_conditional_expression(self, expr);
+    }
+
+    fn visit_logical_expression(&mut self, expr: &LogicalExpression<'a>) {
+        self.add_branch();
+        walk::walk_logical_expression(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_ast_visit::{Visit, walk};
+    use oxc_parser::Parser;
+    use oxc_semantic::{ScopeId, SemanticBuilder};
+    use oxc_span::{GetSpan, SourceType};
+
+    use super::{FunctionMetrics, compute_function_metrics};
+
+    /// Finds the [`ScopeId`] of the innermost function (or, failing that,
+    /// the top-level program) whose span contains `offset`, by re-walking
+    /// the (already semantic-resolved) AST directly -- this needs nothing
+    /// beyond what `compute_function_metrics` itself already relies on.
+    struct FindScope {
+        offset: u32,
+        found: Option<(u32, ScopeId)>,
+    }
+
+    impl FindScope {
+        fn consider(&mut self, span_len: u32, scope_id: ScopeId) {
+            if self.found.is_none_or(|(best, _)| span_len < best) {
+                self.found = Some((span_len, scope_id));
+            }
+        }
+    }
+
+    impl<'a> Visit<'a> for FindScope {
+        fn visit_program(&mut self, program: &oxc_ast::ast::Program<'a>) {
+            let span = program.span();
+            if span.start <= self.offset && self.offset < span.end {
+                self.consider(
+                    span.end - span.start,
+                    program.scope_id.get().expect("scope_id is populated after semantic analysis"),
+                );
+            }
+            walk::walk_program(self, program);
+        }
+
+        fn visit_function(&mut self, func: &oxc_ast::ast::Function<'a>, flags: oxc_semantic::ScopeFlags) {
+            let span = func.span();
+            if span.start <= self.offset && self.offset < span.end {
+                self.consider(
+                    span.end - span.start,
+                    func.scope_id.get().expect("scope_id is populated after semantic analysis"),
+                );
+            }
+            walk::walk_function(self, func, flags);
+        }
+
+        fn visit_arrow_function_expression(
+            &mut self,
+            func: &oxc_ast::ast::ArrowFunctionExpression<'a>,
+        ) {
+            let span = func.span();
+            if span.start <= self.offset && self.offset < span.end {
+                self.consider(
+                    span.end - span.start,
+                    func.scope_id.get().expect("scope_id is populated after semantic analysis"),
+                );
+            }
+            walk::walk_arrow_function_expression(self, func);
+        }
+    }
+
+    /// Parses `source`, builds its `Semantic` (so `scope_id`s are populated),
+    /// and returns the [`FunctionMetrics`] of the innermost function whose
+    /// span contains the first occurrence of `needle`.
+    fn metrics_for(source: &str, needle: &str) -> FunctionMetrics {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::mjs()).parse();
+        SemanticBuilder::new().build(&ret.program);
+        let map = compute_function_metrics(&ret.program);
+
+        let offset = source.find(needle).expect("needle not found in source") as u32;
+        let mut finder = FindScope { offset, found: None };
+        finder.visit_program(&ret.program);
+        let (_, scope_id) = finder.found.expect("no function scope contains the needle");
+
+        *map.get(scope_id).expect("metrics recorded for this function's scope")
+    }
+
+    #[test]
+    fn nested_callbacks_are_counted_relative_to_their_own_function() {
+        // "a(" itself is top-level code, not inside any function -- this
+        // also exercises that top-level callback nesting is recorded
+        // against the program's own scope, not dropped on the floor.
+        let metrics = metrics_for("a(() => b(() => c()));", "a(");
+        assert_eq!(metrics.max_nested_callbacks, 2);
+
+        let metrics = metrics_for("a(() => b(() => c()));", "b(");
+        assert_eq!(metrics.max_nested_callbacks, 1);
+
+        let metrics = metrics_for("a(() => b(() => c()));", "c(");
+        assert_eq!(metrics.max_nested_callbacks, 0);
+    }
+
+    #[test]
+    fn a_function_passed_by_reference_is_not_a_callback() {
+        let metrics = metrics_for("function f() { a(f); }", "function f");
+        assert_eq!(metrics.max_nested_callbacks, 0);
+    }
+
+    #[test]
+    fn max_depth_counts_braceless_bodies() {
+        let metrics = metrics_for("function f() { if (a) if (b) c(); }", "function f");
+        assert_eq!(metrics.max_depth, 2);
+    }
+
+    #[test]
+    fn max_depth_does_not_count_the_function_body_block() {
+        let metrics = metrics_for("function f() { g(); }", "function f");
+        assert_eq!(metrics.max_depth, 0);
+    }
+
+    #[test]
+    fn statement_count_does_not_double_count_a_nested_block() {
+        let metrics = metrics_for("function f() { { g(); h(); } }", "function f");
+        assert_eq!(metrics.statement_count, 2);
+    }
+
+    #[test]
+    fn switch_default_is_not_a_branch() {
+        let metrics = metrics_for(
+            "function f() { switch (x) { case 1: g(); break; default: h(); } }",
+            "function f",
+        );
+        assert_eq!(metrics.cyclomatic_complexity, 2);
+    }
+}
@@ -0,0 +1,3 @@
+mod function_metrics;
+
+pub use function_metrics::{FunctionMetrics, FunctionMetricsMap, compute_function_metrics};